@@ -0,0 +1,336 @@
+//! Methods to construct generic streaming and blocking binary operators.
+
+use std::rc::Rc;
+use std::cell::RefCell;
+
+use progress::{Timestamp, Operate, Antichain};
+use progress::nested::subgraph::{Source, Target};
+use progress::count_map::CountMap;
+use progress::frontier::MutableAntichain;
+
+use dataflow::{Stream, Scope};
+use dataflow::channels::pact::ParallelizationContract;
+use dataflow::channels::pushers::Tee;
+use dataflow::channels::pushers::Counter as PushCounter;
+use dataflow::channels::pushers::buffer::Buffer as PushBuffer;
+use dataflow::channels::pullers::Counter as PullCounter;
+
+use dataflow::operators::handles::{InputHandle, OutputHandle, FrontieredInputHandle, new_input_handle, new_output_handle};
+use dataflow::operators::notify::Notificator;
+
+use Data;
+
+/// Methods to construct generic streaming and blocking binary operators.
+pub trait Binary<G: Scope, D1: Data> {
+    /// Creates a new dataflow operator that partitions its two input streams by two
+    /// parallelization strategies, and repeatedly invokes `logic`, which can read from either
+    /// input stream, write to the output stream, and request notification once a time can no
+    /// longer receive input on either input via the supplied `Notificator`.
+    ///
+    /// #Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Binary};
+    /// use timely::dataflow::channels::pact::Pipeline;
+    ///
+    /// timely::example(|scope| {
+    ///     let stream2 = (0..10).to_stream(scope);
+    ///     (0..10).to_stream(scope)
+    ///            .binary_notify(&stream2, Pipeline, Pipeline, "example", Vec::new(), |input1, input2, output, notificator| {
+    ///                input1.for_each(|cap, data| {
+    ///                    notificator.notify_at(cap.clone());
+    ///                    output.session(&cap).give_content(data);
+    ///                });
+    ///                input2.for_each(|cap, data| {
+    ///                    notificator.notify_at(cap.clone());
+    ///                    output.session(&cap).give_content(data);
+    ///                });
+    ///                notificator.for_each(|cap, _count| {
+    ///                    println!("done with time: {:?}", cap.time());
+    ///                });
+    ///            });
+    /// });
+    /// ```
+    fn binary_notify<D2, D3, L, P1, P2>(&self, other: &Stream<G, D2>, pact1: P1, pact2: P2, name: &str, init: Vec<G::Timestamp>, logic: L) -> Stream<G, D3>
+    where
+        D2: Data,
+        D3: Data,
+        L: FnMut(&mut InputHandle<G::Timestamp, Vec<D1>>,
+                  &mut InputHandle<G::Timestamp, Vec<D2>>,
+                  &mut OutputHandle<G::Timestamp, Vec<D3>, Tee<G::Timestamp, Vec<D3>>>,
+                  &mut Notificator<G::Timestamp>)+'static,
+        P1: ParallelizationContract<G::Timestamp, D1>,
+        P2: ParallelizationContract<G::Timestamp, D2>;
+
+    /// Creates a new dataflow operator that partitions its two input streams by two
+    /// parallelization strategies, and repeatedly invokes `logic`, which can read from either
+    /// input stream and write to the output stream.
+    ///
+    /// #Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Binary};
+    /// use timely::dataflow::channels::pact::Pipeline;
+    ///
+    /// timely::example(|scope| {
+    ///     let stream2 = (0..10).to_stream(scope);
+    ///     (0..10).to_stream(scope)
+    ///            .binary_stream(&stream2, Pipeline, Pipeline, "example", |input1, input2, output| {
+    ///                input1.for_each(|cap, data| { output.session(&cap).give_content(data); });
+    ///                input2.for_each(|cap, data| { output.session(&cap).give_content(data); });
+    ///            });
+    /// });
+    /// ```
+    fn binary_stream<D2, D3, L, P1, P2>(&self, other: &Stream<G, D2>, pact1: P1, pact2: P2, name: &str, logic: L) -> Stream<G, D3>
+    where
+        D2: Data,
+        D3: Data,
+        L: FnMut(&mut InputHandle<G::Timestamp, Vec<D1>>,
+                 &mut InputHandle<G::Timestamp, Vec<D2>>,
+                 &mut OutputHandle<G::Timestamp, Vec<D3>, Tee<G::Timestamp, Vec<D3>>>)+'static,
+        P1: ParallelizationContract<G::Timestamp, D1>,
+        P2: ParallelizationContract<G::Timestamp, D2>;
+
+    /// Creates a new dataflow operator that partitions its two input streams by two
+    /// parallelization strategies, and repeatedly invokes `logic`, which can read from either
+    /// input stream (inspecting the shared input frontier through `FrontieredInputHandle`) and
+    /// write to the output stream.
+    ///
+    /// #Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Binary};
+    /// use timely::dataflow::channels::pact::Pipeline;
+    ///
+    /// timely::example(|scope| {
+    ///     let stream2 = (0..10).to_stream(scope);
+    ///     (0..10).to_stream(scope)
+    ///            .binary_frontier(&stream2, Pipeline, Pipeline, "example", |input1, input2, output| {
+    ///                if input1.frontier().is_empty() && input2.frontier().is_empty() {
+    ///                    input1.for_each(|cap, data| { output.session(&cap).give_content(data); });
+    ///                    input2.for_each(|cap, data| { output.session(&cap).give_content(data); });
+    ///                }
+    ///            });
+    /// });
+    /// ```
+    fn binary_frontier<D2, D3, L, P1, P2>(&self, other: &Stream<G, D2>, pact1: P1, pact2: P2, name: &str, logic: L) -> Stream<G, D3>
+    where
+        D2: Data,
+        D3: Data,
+        L: FnMut(&mut FrontieredInputHandle<G::Timestamp, Vec<D1>>,
+                 &mut FrontieredInputHandle<G::Timestamp, Vec<D2>>,
+                 &mut OutputHandle<G::Timestamp, Vec<D3>, Tee<G::Timestamp, Vec<D3>>>)+'static,
+        P1: ParallelizationContract<G::Timestamp, D1>,
+        P2: ParallelizationContract<G::Timestamp, D2>;
+}
+
+impl<G: Scope, D1: Data> Binary<G, D1> for Stream<G, D1> {
+    fn binary_notify<D2, D3, L, P1, P2>(&self, other: &Stream<G, D2>, pact1: P1, pact2: P2, name: &str, init: Vec<G::Timestamp>, logic: L) -> Stream<G, D3>
+    where
+        D2: Data,
+        D3: Data,
+        L: FnMut(&mut InputHandle<G::Timestamp, Vec<D1>>,
+                  &mut InputHandle<G::Timestamp, Vec<D2>>,
+                  &mut OutputHandle<G::Timestamp, Vec<D3>, Tee<G::Timestamp, Vec<D3>>>,
+                  &mut Notificator<G::Timestamp>)+'static,
+        P1: ParallelizationContract<G::Timestamp, D1>,
+        P2: ParallelizationContract<G::Timestamp, D2>,
+    {
+        let mut scope = self.scope();
+        let channel_id1 = scope.new_identifier();
+        let channel_id2 = scope.new_identifier();
+
+        let (sender1, receiver1) = pact1.connect(&mut scope, channel_id1);
+        let (sender2, receiver2) = pact2.connect(&mut scope, channel_id2);
+        let (targets, registrar) = Tee::<G::Timestamp, Vec<D3>>::new();
+        let internal = Rc::new(RefCell::new(CountMap::new()));
+
+        let index = scope.allocate_operator_index();
+        let address = scope.addr();
+
+        let operator = BinaryOperator {
+            name: name.to_owned(),
+            address: address,
+            pull_counter1: PullCounter::new(receiver1),
+            pull_counter2: PullCounter::new(receiver2),
+            push_buffer: PushBuffer::new(PushCounter::new(targets, internal.clone())),
+            internal: internal,
+            frontier: MutableAntichain::new_bottom(init.clone()),
+            notify: init,
+            logic: logic,
+        };
+
+        scope.add_operator_with_index(operator, index);
+        self.connect_to(Target { index: index, port: 0 }, sender1, channel_id1);
+        other.connect_to(Target { index: index, port: 1 }, sender2, channel_id2);
+
+        Stream::new(Source { index: index, port: 0 }, registrar, scope)
+    }
+
+    fn binary_stream<D2, D3, L, P1, P2>(&self, other: &Stream<G, D2>, pact1: P1, pact2: P2, name: &str, logic: L) -> Stream<G, D3>
+    where
+        D2: Data,
+        D3: Data,
+        L: FnMut(&mut InputHandle<G::Timestamp, Vec<D1>>,
+                 &mut InputHandle<G::Timestamp, Vec<D2>>,
+                 &mut OutputHandle<G::Timestamp, Vec<D3>, Tee<G::Timestamp, Vec<D3>>>)+'static,
+        P1: ParallelizationContract<G::Timestamp, D1>,
+        P2: ParallelizationContract<G::Timestamp, D2>,
+    {
+        self.binary_notify(other, pact1, pact2, name, Vec::new(), move |input1, input2, output, _notificator| logic(input1, input2, output))
+    }
+
+    fn binary_frontier<D2, D3, L, P1, P2>(&self, other: &Stream<G, D2>, pact1: P1, pact2: P2, name: &str, logic: L) -> Stream<G, D3>
+    where
+        D2: Data,
+        D3: Data,
+        L: FnMut(&mut FrontieredInputHandle<G::Timestamp, Vec<D1>>,
+                 &mut FrontieredInputHandle<G::Timestamp, Vec<D2>>,
+                 &mut OutputHandle<G::Timestamp, Vec<D3>, Tee<G::Timestamp, Vec<D3>>>)+'static,
+        P1: ParallelizationContract<G::Timestamp, D1>,
+        P2: ParallelizationContract<G::Timestamp, D2>,
+    {
+        let mut scope = self.scope();
+        let channel_id1 = scope.new_identifier();
+        let channel_id2 = scope.new_identifier();
+
+        let (sender1, receiver1) = pact1.connect(&mut scope, channel_id1);
+        let (sender2, receiver2) = pact2.connect(&mut scope, channel_id2);
+        let (targets, registrar) = Tee::<G::Timestamp, Vec<D3>>::new();
+        let internal = Rc::new(RefCell::new(CountMap::new()));
+
+        let index = scope.allocate_operator_index();
+        let address = scope.addr();
+
+        let operator = BinaryFrontierOperator {
+            name: name.to_owned(),
+            address: address,
+            pull_counter1: PullCounter::new(receiver1),
+            pull_counter2: PullCounter::new(receiver2),
+            push_buffer: PushBuffer::new(PushCounter::new(targets, internal.clone())),
+            internal: internal,
+            frontier: MutableAntichain::new(),
+            logic: logic,
+        };
+
+        scope.add_operator_with_index(operator, index);
+        self.connect_to(Target { index: index, port: 0 }, sender1, channel_id1);
+        other.connect_to(Target { index: index, port: 1 }, sender2, channel_id2);
+
+        Stream::new(Source { index: index, port: 0 }, registrar, scope)
+    }
+}
+
+/// The `Operate` implementation backing `binary_notify` and `binary_stream`.
+struct BinaryOperator<T: Timestamp, D1, D2, D3, L>
+where L: FnMut(&mut InputHandle<T, Vec<D1>>, &mut InputHandle<T, Vec<D2>>, &mut OutputHandle<T, Vec<D3>, Tee<T, Vec<D3>>>, &mut Notificator<T>)+'static {
+    name: String,
+    address: Vec<usize>,
+    pull_counter1: PullCounter<T, Vec<D1>>,
+    pull_counter2: PullCounter<T, Vec<D2>>,
+    push_buffer: PushBuffer<T, Vec<D3>, PushCounter<T, Vec<D3>, Tee<T, Vec<D3>>>>,
+    internal: Rc<RefCell<CountMap<T>>>,
+    frontier: MutableAntichain<T>,
+    notify: Vec<T>,
+    logic: L,
+}
+
+impl<T: Timestamp, D1, D2, D3, L> Operate<T> for BinaryOperator<T, D1, D2, D3, L>
+where L: FnMut(&mut InputHandle<T, Vec<D1>>, &mut InputHandle<T, Vec<D2>>, &mut OutputHandle<T, Vec<D3>, Tee<T, Vec<D3>>>, &mut Notificator<T>)+'static {
+
+    fn name(&self) -> String { self.name.clone() }
+    fn inputs(&self) -> usize { 2 }
+    fn outputs(&self) -> usize { 1 }
+
+    fn get_internal_summary(&mut self) -> (Vec<Vec<Antichain<T::Summary>>>, Vec<CountMap<T>>) {
+        let mut initial = CountMap::new();
+        for time in self.notify.drain(..) {
+            initial.update(&time, 1);
+        }
+        (vec![vec![Antichain::from_elem(Default::default())], vec![Antichain::from_elem(Default::default())]], vec![initial])
+    }
+
+    fn push_external_progress(&mut self, external: &mut [CountMap<T>]) {
+        for input in external.iter_mut() {
+            for (time, delta) in input.drain() {
+                self.frontier.update_weight(&time, delta, &mut CountMap::new());
+            }
+        }
+    }
+
+    fn pull_internal_progress(&mut self, internal: &mut [CountMap<T>],
+                                          consumed: &mut [CountMap<T>],
+                                          produced: &mut [CountMap<T>]) -> bool {
+        let mut input_handle1 = new_input_handle(&mut self.pull_counter1, self.internal.clone(), &self.address);
+        let mut input_handle2 = new_input_handle(&mut self.pull_counter2, self.internal.clone(), &self.address);
+        let mut output_handle = new_output_handle(&mut self.push_buffer);
+        let mut notificator = Notificator::new(&self.frontier);
+
+        ::logging::log(&::logging::OPERATOR_SCHEDULE, &self.address, true);
+        (self.logic)(&mut input_handle1, &mut input_handle2, &mut output_handle, &mut notificator);
+        ::logging::log(&::logging::OPERATOR_SCHEDULE, &self.address, false);
+        ::logging::log_counts(&self.address, input_handle1.consumed() + input_handle2.consumed());
+
+        self.push_buffer.cease();
+        self.pull_counter1.pull_progress(&mut consumed[0]);
+        self.pull_counter2.pull_progress(&mut consumed[1]);
+        self.push_buffer.inner().pull_progress(&mut produced[0]);
+        self.internal.borrow_mut().drain_into(&mut internal[0]);
+
+        false
+    }
+}
+
+/// The `Operate` implementation backing `binary_frontier`.
+struct BinaryFrontierOperator<T: Timestamp, D1, D2, D3, L>
+where L: FnMut(&mut FrontieredInputHandle<T, Vec<D1>>, &mut FrontieredInputHandle<T, Vec<D2>>, &mut OutputHandle<T, Vec<D3>, Tee<T, Vec<D3>>>)+'static {
+    name: String,
+    address: Vec<usize>,
+    pull_counter1: PullCounter<T, Vec<D1>>,
+    pull_counter2: PullCounter<T, Vec<D2>>,
+    push_buffer: PushBuffer<T, Vec<D3>, PushCounter<T, Vec<D3>, Tee<T, Vec<D3>>>>,
+    internal: Rc<RefCell<CountMap<T>>>,
+    frontier: MutableAntichain<T>,
+    logic: L,
+}
+
+impl<T: Timestamp, D1, D2, D3, L> Operate<T> for BinaryFrontierOperator<T, D1, D2, D3, L>
+where L: FnMut(&mut FrontieredInputHandle<T, Vec<D1>>, &mut FrontieredInputHandle<T, Vec<D2>>, &mut OutputHandle<T, Vec<D3>, Tee<T, Vec<D3>>>)+'static {
+
+    fn name(&self) -> String { self.name.clone() }
+    fn inputs(&self) -> usize { 2 }
+    fn outputs(&self) -> usize { 1 }
+
+    fn get_internal_summary(&mut self) -> (Vec<Vec<Antichain<T::Summary>>>, Vec<CountMap<T>>) {
+        (vec![vec![Antichain::from_elem(Default::default())], vec![Antichain::from_elem(Default::default())]], vec![CountMap::new()])
+    }
+
+    fn push_external_progress(&mut self, external: &mut [CountMap<T>]) {
+        for input in external.iter_mut() {
+            for (time, delta) in input.drain() {
+                self.frontier.update_weight(&time, delta, &mut CountMap::new());
+            }
+        }
+    }
+
+    fn pull_internal_progress(&mut self, internal: &mut [CountMap<T>],
+                                          consumed: &mut [CountMap<T>],
+                                          produced: &mut [CountMap<T>]) -> bool {
+        let frontier = &self.frontier;
+        let mut raw_input1 = new_input_handle(&mut self.pull_counter1, self.internal.clone(), &self.address);
+        let mut raw_input2 = new_input_handle(&mut self.pull_counter2, self.internal.clone(), &self.address);
+        let mut input_handle1 = FrontieredInputHandle::new(&mut raw_input1, frontier);
+        let mut input_handle2 = FrontieredInputHandle::new(&mut raw_input2, frontier);
+        let mut output_handle = new_output_handle(&mut self.push_buffer);
+
+        ::logging::log(&::logging::OPERATOR_SCHEDULE, &self.address, true);
+        (self.logic)(&mut input_handle1, &mut input_handle2, &mut output_handle);
+        ::logging::log(&::logging::OPERATOR_SCHEDULE, &self.address, false);
+        ::logging::log_counts(&self.address, input_handle1.handle.consumed() + input_handle2.handle.consumed());
+
+        self.push_buffer.cease();
+        self.pull_counter1.pull_progress(&mut consumed[0]);
+        self.pull_counter2.pull_progress(&mut consumed[1]);
+        self.push_buffer.inner().pull_progress(&mut produced[0]);
+        self.internal.borrow_mut().drain_into(&mut internal[0]);
+
+        false
+    }
+}
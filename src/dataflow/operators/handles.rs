@@ -2,35 +2,81 @@
 
 use std::rc::Rc;
 use std::cell::RefCell;
+use std::mem;
 use progress::Timestamp;
 use progress::count_map::CountMap;
+use progress::frontier::MutableAntichain;
 use dataflow::channels::pullers::Counter as PullCounter;
 use dataflow::channels::pushers::Counter as PushCounter;
 use dataflow::channels::pushers::buffer::{Buffer, Session};
-use dataflow::channels::Content;
 use timely_communication::Push;
 
 use dataflow::operators::Capability;
 use dataflow::operators::capability::mint as mint_capability;
 
+/// A batch of records that can be moved through a dataflow edge as a single unit.
+///
+/// Handles and channels were previously fixed to `Content<D>`, a buffer of individual `D`
+/// records, which forces every operator to pay per-record push/pull overhead even when the
+/// underlying data would be happy to travel in a more compact, columnar layout. Implementing
+/// `Container` lets a type stand in for `Content<D>` on any edge: `InputHandle`, `OutputHandle`,
+/// `Buffer`, and `Session` are all generic over `C: Container` rather than hard-coded to
+/// `Content<D>`. `swap` is what lets a batch flow through a `Session` as a single unit: a
+/// `Buffer` hands its filled container to the channel and takes back an empty one in its place,
+/// without ever decomposing the batch into individual records.
+pub trait Container {
+    /// The number of records held by the container.
+    fn len(&self) -> usize;
+
+    /// True iff the container holds no records.
+    #[inline]
+    fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Removes all records from the container, without necessarily releasing its allocation.
+    fn clear(&mut self);
+
+    /// Exchanges the contents of `self` and `other` as a single batch, without inspecting or
+    /// moving individual records.
+    #[inline]
+    fn swap(&mut self, other: &mut Self) where Self: Sized {
+        mem::swap(self, other);
+    }
+}
+
+impl<D> Container for Vec<D> {
+    #[inline]
+    fn len(&self) -> usize { Vec::len(self) }
+
+    #[inline]
+    fn clear(&mut self) { Vec::clear(self) }
+}
+
 /// Handle to an operator's input stream.
-pub struct InputHandle<'a, T: Timestamp, D: 'a> {
-    pull_counter: &'a mut PullCounter<T, D>,
+pub struct InputHandle<'a, T: Timestamp, C: Container+'a> {
+    pull_counter: &'a mut PullCounter<T, C>,
     internal: Rc<RefCell<CountMap<T>>>,
+    address: &'a [usize],
+    pulled: usize,
 }
 
-impl<'a, T: Timestamp, D> InputHandle<'a, T, D> {
+impl<'a, T: Timestamp, C: Container> InputHandle<'a, T, C> {
     /// Reads the next input buffer (at some timestamp `t`) and a corresponding capability for `t`.
     /// The timestamp `t` of the input buffer can be retrieved by invoking `.time()` on the capability.
     /// Returns `None` when there's no more data available.
     #[inline]
-    pub fn next(&mut self) -> Option<(Capability<T>, &mut Content<D>)> {
+    pub fn next(&mut self) -> Option<(Capability<T>, &mut C)> {
         let internal = &mut self.internal;
+        let pulled = &mut self.pulled;
         self.pull_counter.next().map(|(&time, content)| {
+            *pulled += content.len();
             (mint_capability(time, internal.clone()), content)
         })
     }
 
+    /// The number of records read from this input via `next`/`for_each` so far.
+    #[inline]
+    pub fn consumed(&self) -> usize { self.pulled }
+
     /// Repeatedly calls `logic` till exhaustion of the available input data.
     /// `logic` receives a capability and an input buffer.
     ///
@@ -49,30 +95,77 @@ impl<'a, T: Timestamp, D> InputHandle<'a, T, D> {
     /// });
     /// ```
     #[inline]
-    pub fn for_each<F: FnMut(Capability<T>, &mut Content<D>)>(&mut self, mut logic: F) {
+    pub fn for_each<F: FnMut(Capability<T>, &mut C)>(&mut self, mut logic: F) {
         while let Some((cap, data)) = self.next() {
-            ::logging::log(&::logging::GUARDED_MESSAGE, true);
+            ::logging::log(&::logging::GUARDED_MESSAGE, self.address, true);
             logic(cap, data);
-            ::logging::log(&::logging::GUARDED_MESSAGE, false);
+            ::logging::log(&::logging::GUARDED_MESSAGE, self.address, false);
         }
     }
 }
 
 /// Constructs an input handle.
 /// Declared separately so that it can be kept private when InputHandle is re-exported.
-pub fn new_input_handle<'a, T: Timestamp, D: 'a>(pull_counter: &'a mut PullCounter<T, D>, internal: Rc<RefCell<CountMap<T>>>) -> InputHandle<'a, T, D> {
+pub fn new_input_handle<'a, T: Timestamp, C: Container+'a>(pull_counter: &'a mut PullCounter<T, C>, internal: Rc<RefCell<CountMap<T>>>, address: &'a [usize]) -> InputHandle<'a, T, C> {
     InputHandle {
         pull_counter: pull_counter,
         internal: internal,
+        address: address,
+        pulled: 0,
+    }
+}
+
+/// Handle to an operator's input stream, augmented with the operator's input frontier.
+///
+/// `FrontieredInputHandle` wraps an `InputHandle`, additionally exposing the frontier of
+/// timestamps that the operator's inputs may still deliver. An operator with several inputs
+/// hands the same frontier, covering all of its inputs, to each of its handles. Operators that must buffer
+/// inputs until a timestamp can no longer arrive (for example, an operator that joins two
+/// streams and needs to know when it has seen everything for a given time) can consult
+/// `.frontier()` before committing to produce output.
+pub struct FrontieredInputHandle<'a, 'b: 'a, T: Timestamp+'b, C: Container+'b> {
+    /// The underlying input handle.
+    pub handle: &'a mut InputHandle<'b, T, C>,
+    /// The frontier of timestamps that may still be received on the operator's inputs.
+    pub frontier: &'a MutableAntichain<T>,
+}
+
+impl<'a, 'b: 'a, T: Timestamp, C: Container> FrontieredInputHandle<'a, 'b, T, C> {
+    /// Allocates a new `FrontieredInputHandle` from an `InputHandle` and a frontier.
+    pub fn new(handle: &'a mut InputHandle<'b, T, C>, frontier: &'a MutableAntichain<T>) -> Self {
+        FrontieredInputHandle {
+            handle: handle,
+            frontier: frontier,
+        }
+    }
+
+    /// Reads the next input buffer (at some timestamp `t`) and a corresponding capability for `t`.
+    /// Returns `None` when there's no more data available.
+    #[inline]
+    pub fn next(&mut self) -> Option<(Capability<T>, &mut C)> {
+        self.handle.next()
+    }
+
+    /// Repeatedly calls `logic` till exhaustion of the available input data.
+    /// `logic` receives a capability and an input buffer.
+    #[inline]
+    pub fn for_each<F: FnMut(Capability<T>, &mut C)>(&mut self, logic: F) {
+        self.handle.for_each(logic);
+    }
+
+    /// Inspects the frontier of timestamps that may still arrive on the operator's inputs.
+    #[inline]
+    pub fn frontier(&self) -> &[T] {
+        self.frontier.frontier()
     }
 }
 
 /// Handle to an operator's output stream.
-pub struct OutputHandle<'a, T: Timestamp, D: 'a, P: Push<(T, Content<D>)>+'a> {
-    push_buffer: &'a mut Buffer<T, D, PushCounter<T, D, P>>,
+pub struct OutputHandle<'a, T: Timestamp, C: Container+'a, P: Push<(T, C)>+'a> {
+    push_buffer: &'a mut Buffer<T, C, PushCounter<T, C, P>>,
 }
 
-impl<'a, T: Timestamp, D, P: Push<(T, Content<D>)>> OutputHandle<'a, T, D, P> {
+impl<'a, T: Timestamp, C: Container, P: Push<(T, C)>> OutputHandle<'a, T, C, P> {
     /// Obtains a session that can send data at the timestamp associated with capability `cap`.
     ///
     /// In order to send data at a future timestamp, obtain a capability for the new timestamp
@@ -94,17 +187,15 @@ impl<'a, T: Timestamp, D, P: Push<(T, Content<D>)>> OutputHandle<'a, T, D, P> {
     ///            });
     /// });
     /// ```
-    pub fn session<'b>(&'b mut self, cap: &Capability<T>) -> Session<'b, T, D, PushCounter<T, D, P>> where 'a: 'b {
+    pub fn session<'b>(&'b mut self, cap: &Capability<T>) -> Session<'b, T, C, PushCounter<T, C, P>> where 'a: 'b {
         self.push_buffer.session(cap)
     }
 }
 
 /// Constructs an output handle.
 /// Declared separately so that it can be kept private when OutputHandle is re-exported.
-pub fn new_output_handle<'a, T: Timestamp, D, P: Push<(T, Content<D>)>>(push_buffer: &'a mut Buffer<T, D, PushCounter<T, D, P>>) -> OutputHandle<'a, T, D, P> {
+pub fn new_output_handle<'a, T: Timestamp, C: Container, P: Push<(T, C)>>(push_buffer: &'a mut Buffer<T, C, PushCounter<T, C, P>>) -> OutputHandle<'a, T, C, P> {
     OutputHandle {
         push_buffer: push_buffer,
     }
 }
-
-
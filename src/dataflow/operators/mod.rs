@@ -0,0 +1,15 @@
+//! Extension traits and types for `Stream` implementing various operators.
+
+pub use self::handles::{InputHandle, OutputHandle, FrontieredInputHandle};
+pub use self::notify::Notificator;
+pub use self::unary::Unary;
+pub use self::binary::Binary;
+pub use self::operator_info::OperatorInfo;
+pub use self::source::source;
+
+pub mod handles;
+pub mod notify;
+pub mod unary;
+pub mod binary;
+pub mod operator_info;
+pub mod source;
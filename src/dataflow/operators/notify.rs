@@ -0,0 +1,79 @@
+//! Tracks progress of a dataflow operator's input, and delivers notifications once timestamps
+//! can no longer be received.
+
+use progress::Timestamp;
+use progress::frontier::MutableAntichain;
+
+use dataflow::operators::Capability;
+
+/// Tracks requests for notification and delivers available notifications.
+///
+/// A `Notificator` holds a list of outstanding `notify_at` requests, each a `Capability<T>`
+/// together with a count of how many times it has been requested. On each call to `for_each`,
+/// the Notificator compares the time of each pending request against the current input
+/// frontier; any request whose time can no longer be reached by future input (no frontier
+/// element is less-or-equal to it) is delivered to `logic`. `T` is only partially ordered, so
+/// pending requests are not sorted before delivery: every ready request is delivered, regardless
+/// of how it compares to other ready or still-blocked requests. Requests still in advance of the
+/// frontier are retained for a future call.
+pub struct Notificator<'a, T: Timestamp> {
+    frontier: &'a MutableAntichain<T>,
+    pending: Vec<(Capability<T>, u64)>,
+}
+
+impl<'a, T: Timestamp> Notificator<'a, T> {
+    /// Allocates a new `Notificator` with no pending requests, from a frontier reference.
+    pub fn new(frontier: &'a MutableAntichain<T>) -> Notificator<'a, T> {
+        Notificator {
+            frontier: frontier,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Requests a notification at the time associated with capability `cap`.
+    ///
+    /// Multiple requests for the same time are coalesced into a single pending notification,
+    /// whose count is incremented for each request.
+    ///
+    /// In order to request a notification at a *future* timestamp, obtain a capability for the
+    /// new timestamp first (e.g. via `Capability::delayed`) and pass that capability.
+    ///
+    /// #Examples
+    /// ```ignore
+    /// notificator.notify_at(capability);
+    /// ```
+    #[inline]
+    pub fn notify_at(&mut self, cap: Capability<T>) {
+        for &mut (ref pending_cap, ref mut count) in &mut self.pending {
+            if pending_cap.time() == cap.time() {
+                *count += 1;
+                return;
+            }
+        }
+        self.pending.push((cap, 1));
+    }
+
+    /// Repeatedly calls `logic` until all available notifications have been delivered.
+    ///
+    /// `logic` receives a capability for `t`, the timestamp being notified, and the number of
+    /// `notify_at` calls (possibly with distinct, but equal, capabilities) that coalesced to
+    /// produce it.
+    #[inline]
+    pub fn for_each<F: FnMut(Capability<T>, u64)>(&mut self, mut logic: F) {
+        let mut index = 0;
+        while index < self.pending.len() {
+            if Self::ready(&self.pending[index].0, self.frontier) {
+                let (cap, count) = self.pending.remove(index);
+                logic(cap, count);
+            }
+            else {
+                index += 1;
+            }
+        }
+    }
+
+    /// True iff `cap`'s time can no longer be reached by input yet to arrive at `frontier`.
+    fn ready(cap: &Capability<T>, frontier: &MutableAntichain<T>) -> bool {
+        !frontier.frontier().iter().any(|t| t.le(&cap.time()))
+    }
+}
@@ -0,0 +1,26 @@
+//! Identifying information about an operator, handed to operator constructors.
+
+/// Information about the operator a constructor is building, sufficient to let the operator
+/// name itself in logs, seed deterministic per-operator state, or partition external resources
+/// by its position in the scope hierarchy.
+#[derive(Debug, Clone)]
+pub struct OperatorInfo {
+    /// The operator's index within its immediate enclosing scope, from
+    /// `Scope::allocate_operator_index`. Unique among operators added to that one scope, but
+    /// *not* unique across the worker as a whole: an operator in a different (e.g. nested) scope
+    /// can be allocated the same index. Combine with `address`, which locates the enclosing
+    /// scope, to get a worker-wide identity.
+    pub local_id: usize,
+    /// The path of scope indices from the root down to the scope containing this operator.
+    pub address: Vec<usize>,
+}
+
+impl OperatorInfo {
+    /// Allocates a new `OperatorInfo` from a scope-local operator index and scope address.
+    pub fn new(local_id: usize, address: Vec<usize>) -> OperatorInfo {
+        OperatorInfo {
+            local_id: local_id,
+            address: address,
+        }
+    }
+}
@@ -0,0 +1,118 @@
+//! Construct a new dataflow source, producing data out of thin air.
+
+use std::rc::Rc;
+use std::cell::RefCell;
+
+use progress::{Timestamp, Operate, Antichain};
+use progress::nested::subgraph::Source as SourcePort;
+use progress::count_map::CountMap;
+
+use dataflow::{Stream, Scope};
+use dataflow::channels::pushers::Tee;
+use dataflow::channels::pushers::Counter as PushCounter;
+use dataflow::channels::pushers::buffer::Buffer as PushBuffer;
+
+use dataflow::operators::Capability;
+use dataflow::operators::capability::mint as mint_capability;
+use dataflow::operators::handles::{OutputHandle, new_output_handle};
+use dataflow::operators::operator_info::OperatorInfo;
+
+use Data;
+
+/// Creates a new dataflow source that, given a capability for the default timestamp and an
+/// `OperatorInfo` naming the new operator, constructs the `FnMut` that will be invoked
+/// repeatedly to produce output.
+///
+/// The two-phase construction lets `construct` stash the capability (to retain the ability to
+/// send in the future) and seed any per-operator state keyed off `info.address` together with
+/// `info.local_id`, before handing
+/// back the steady-state logic.
+///
+/// #Examples
+/// ```
+/// use timely::dataflow::operators::{source, Inspect};
+///
+/// timely::example(|scope| {
+///     source(scope, "Source", |capability, _info| {
+///         let mut cap = Some(capability);
+///         move |output| {
+///             let mut done = false;
+///             if let Some(cap) = cap.take() {
+///                 output.session(&cap).give(0);
+///                 done = true;
+///             }
+///             let _ = done;
+///         }
+///     })
+///     .inspect(|x| println!("seen: {:?}", x));
+/// });
+/// ```
+pub fn source<G: Scope, D1: Data, B, L>(scope: &G, name: &str, construct: B) -> Stream<G, D1>
+where
+    B: FnOnce(Capability<G::Timestamp>, OperatorInfo) -> L,
+    L: FnMut(&mut OutputHandle<G::Timestamp, Vec<D1>, Tee<G::Timestamp, Vec<D1>>>)+'static,
+{
+    let mut scope = scope.clone();
+
+    let (targets, registrar) = Tee::<G::Timestamp, Vec<D1>>::new();
+    let internal = Rc::new(RefCell::new(CountMap::new()));
+
+    let index = scope.allocate_operator_index();
+    let address = scope.addr();
+    let info = OperatorInfo::new(index, address.clone());
+
+    let cap = mint_capability(Default::default(), internal.clone());
+    internal.borrow_mut().update(&Default::default(), 1);
+
+    let logic = construct(cap, info);
+
+    let operator = SourceOperator {
+        name: name.to_owned(),
+        address: address,
+        push_buffer: PushBuffer::new(PushCounter::new(targets, internal.clone())),
+        internal: internal,
+        logic: logic,
+    };
+
+    scope.add_operator_with_index(operator, index);
+    Stream::new(SourcePort { index: index, port: 0 }, registrar, scope)
+}
+
+/// The `Operate` implementation backing `source`.
+struct SourceOperator<T: Timestamp, D1, L>
+where L: FnMut(&mut OutputHandle<T, Vec<D1>, Tee<T, Vec<D1>>>)+'static {
+    name: String,
+    address: Vec<usize>,
+    push_buffer: PushBuffer<T, Vec<D1>, PushCounter<T, Vec<D1>, Tee<T, Vec<D1>>>>,
+    internal: Rc<RefCell<CountMap<T>>>,
+    logic: L,
+}
+
+impl<T: Timestamp, D1, L> Operate<T> for SourceOperator<T, D1, L>
+where L: FnMut(&mut OutputHandle<T, Vec<D1>, Tee<T, Vec<D1>>>)+'static {
+
+    fn name(&self) -> String { self.name.clone() }
+    fn inputs(&self) -> usize { 0 }
+    fn outputs(&self) -> usize { 1 }
+
+    fn get_internal_summary(&mut self) -> (Vec<Vec<Antichain<T::Summary>>>, Vec<CountMap<T>>) {
+        let mut initial = CountMap::new();
+        self.internal.borrow_mut().drain_into(&mut initial);
+        (vec![], vec![initial])
+    }
+
+    fn pull_internal_progress(&mut self, internal: &mut [CountMap<T>],
+                                          _consumed: &mut [CountMap<T>],
+                                          produced: &mut [CountMap<T>]) -> bool {
+        let mut output_handle = new_output_handle(&mut self.push_buffer);
+        ::logging::log(&::logging::OPERATOR_SCHEDULE, &self.address, true);
+        (self.logic)(&mut output_handle);
+        ::logging::log(&::logging::OPERATOR_SCHEDULE, &self.address, false);
+
+        self.push_buffer.cease();
+        self.push_buffer.inner().pull_progress(&mut produced[0]);
+        self.internal.borrow_mut().drain_into(&mut internal[0]);
+
+        false
+    }
+}
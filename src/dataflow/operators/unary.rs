@@ -0,0 +1,295 @@
+//! Methods to construct generic streaming and blocking unary operators.
+
+use std::rc::Rc;
+use std::cell::RefCell;
+
+use progress::{Timestamp, Operate, Antichain};
+use progress::nested::subgraph::{Source, Target};
+use progress::count_map::CountMap;
+use progress::frontier::MutableAntichain;
+
+use dataflow::{Stream, Scope};
+use dataflow::channels::pact::ParallelizationContract;
+use dataflow::channels::pushers::Tee;
+use dataflow::channels::pushers::Counter as PushCounter;
+use dataflow::channels::pushers::buffer::Buffer as PushBuffer;
+use dataflow::channels::pullers::Counter as PullCounter;
+
+use dataflow::operators::handles::{InputHandle, OutputHandle, FrontieredInputHandle, new_input_handle, new_output_handle};
+use dataflow::operators::notify::Notificator;
+
+use Data;
+
+/// Methods to construct generic streaming and blocking unary operators.
+pub trait Unary<G: Scope, D1: Data> {
+    /// Creates a new dataflow operator that partitions its input stream by a parallelization
+    /// strategy `pact`, and repeatedly invokes `logic`, which can read from the input stream,
+    /// write to the output stream, and request notification once a time can no longer receive
+    /// input via the supplied `Notificator`.
+    ///
+    /// #Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Unary};
+    /// use timely::dataflow::channels::pact::Pipeline;
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .unary_notify(Pipeline, "example", Vec::new(), |input, output, notificator| {
+    ///                input.for_each(|cap, data| {
+    ///                    notificator.notify_at(cap.clone());
+    ///                    output.session(&cap).give_content(data);
+    ///                });
+    ///                notificator.for_each(|cap, _count| {
+    ///                    println!("done with time: {:?}", cap.time());
+    ///                });
+    ///            });
+    /// });
+    /// ```
+    fn unary_notify<D2, L, P>(&self, pact: P, name: &str, init: Vec<G::Timestamp>, logic: L) -> Stream<G, D2>
+    where
+        D2: Data,
+        L: FnMut(&mut InputHandle<G::Timestamp, Vec<D1>>,
+                  &mut OutputHandle<G::Timestamp, Vec<D2>, Tee<G::Timestamp, Vec<D2>>>,
+                  &mut Notificator<G::Timestamp>)+'static,
+        P: ParallelizationContract<G::Timestamp, D1>;
+
+    /// Creates a new dataflow operator that partitions its input stream by a parallelization
+    /// strategy `pact`, and repeatedly invokes `logic`, which can read from the input stream
+    /// and write to the output stream.
+    ///
+    /// #Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Unary};
+    /// use timely::dataflow::channels::pact::Pipeline;
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .unary_stream(Pipeline, "example", |input, output| {
+    ///                input.for_each(|cap, data| {
+    ///                    output.session(&cap).give_content(data);
+    ///                });
+    ///            });
+    /// });
+    /// ```
+    fn unary_stream<D2, L, P>(&self, pact: P, name: &str, logic: L) -> Stream<G, D2>
+    where
+        D2: Data,
+        L: FnMut(&mut InputHandle<G::Timestamp, Vec<D1>>,
+                 &mut OutputHandle<G::Timestamp, Vec<D2>, Tee<G::Timestamp, Vec<D2>>>)+'static,
+        P: ParallelizationContract<G::Timestamp, D1>;
+
+    /// Creates a new dataflow operator that partitions its input stream by a parallelization
+    /// strategy `pact`, and repeatedly invokes `logic`, which can read from the input stream
+    /// (inspecting the input frontier through the `FrontieredInputHandle`) and write to the
+    /// output stream. Unlike `unary_notify`, `logic` consults the frontier directly rather than
+    /// registering notification requests.
+    ///
+    /// #Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Unary};
+    /// use timely::dataflow::channels::pact::Pipeline;
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .unary_frontier(Pipeline, "example", |input, output| {
+    ///                if input.frontier().is_empty() {
+    ///                    input.for_each(|cap, data| {
+    ///                        output.session(&cap).give_content(data);
+    ///                    });
+    ///                }
+    ///            });
+    /// });
+    /// ```
+    fn unary_frontier<D2, L, P>(&self, pact: P, name: &str, logic: L) -> Stream<G, D2>
+    where
+        D2: Data,
+        L: FnMut(&mut FrontieredInputHandle<G::Timestamp, Vec<D1>>,
+                 &mut OutputHandle<G::Timestamp, Vec<D2>, Tee<G::Timestamp, Vec<D2>>>)+'static,
+        P: ParallelizationContract<G::Timestamp, D1>;
+}
+
+impl<G: Scope, D1: Data> Unary<G, D1> for Stream<G, D1> {
+    fn unary_notify<D2, L, P>(&self, pact: P, name: &str, init: Vec<G::Timestamp>, logic: L) -> Stream<G, D2>
+    where
+        D2: Data,
+        L: FnMut(&mut InputHandle<G::Timestamp, Vec<D1>>,
+                  &mut OutputHandle<G::Timestamp, Vec<D2>, Tee<G::Timestamp, Vec<D2>>>,
+                  &mut Notificator<G::Timestamp>)+'static,
+        P: ParallelizationContract<G::Timestamp, D1>,
+    {
+        let mut scope = self.scope();
+        let channel_id = scope.new_identifier();
+
+        let (sender, receiver) = pact.connect(&mut scope, channel_id);
+        let (targets, registrar) = Tee::<G::Timestamp, Vec<D2>>::new();
+        let internal = Rc::new(RefCell::new(CountMap::new()));
+
+        let index = scope.allocate_operator_index();
+        let address = scope.addr();
+
+        let operator = UnaryOperator {
+            name: name.to_owned(),
+            address: address,
+            pull_counter: PullCounter::new(receiver),
+            push_buffer: PushBuffer::new(PushCounter::new(targets, internal.clone())),
+            internal: internal,
+            frontier: MutableAntichain::new_bottom(init.clone()),
+            notify: init,
+            logic: logic,
+        };
+
+        scope.add_operator_with_index(operator, index);
+        self.connect_to(Target { index: index, port: 0 }, sender, channel_id);
+
+        Stream::new(Source { index: index, port: 0 }, registrar, scope)
+    }
+
+    fn unary_stream<D2, L, P>(&self, pact: P, name: &str, logic: L) -> Stream<G, D2>
+    where
+        D2: Data,
+        L: FnMut(&mut InputHandle<G::Timestamp, Vec<D1>>,
+                 &mut OutputHandle<G::Timestamp, Vec<D2>, Tee<G::Timestamp, Vec<D2>>>)+'static,
+        P: ParallelizationContract<G::Timestamp, D1>,
+    {
+        self.unary_notify(pact, name, Vec::new(), move |input, output, _notificator| logic(input, output))
+    }
+
+    fn unary_frontier<D2, L, P>(&self, pact: P, name: &str, logic: L) -> Stream<G, D2>
+    where
+        D2: Data,
+        L: FnMut(&mut FrontieredInputHandle<G::Timestamp, Vec<D1>>,
+                 &mut OutputHandle<G::Timestamp, Vec<D2>, Tee<G::Timestamp, Vec<D2>>>)+'static,
+        P: ParallelizationContract<G::Timestamp, D1>,
+    {
+        let mut scope = self.scope();
+        let channel_id = scope.new_identifier();
+
+        let (sender, receiver) = pact.connect(&mut scope, channel_id);
+        let (targets, registrar) = Tee::<G::Timestamp, Vec<D2>>::new();
+        let internal = Rc::new(RefCell::new(CountMap::new()));
+
+        let index = scope.allocate_operator_index();
+        let address = scope.addr();
+
+        let operator = UnaryFrontierOperator {
+            name: name.to_owned(),
+            address: address,
+            pull_counter: PullCounter::new(receiver),
+            push_buffer: PushBuffer::new(PushCounter::new(targets, internal.clone())),
+            internal: internal,
+            frontier: MutableAntichain::new(),
+            logic: logic,
+        };
+
+        scope.add_operator_with_index(operator, index);
+        self.connect_to(Target { index: index, port: 0 }, sender, channel_id);
+
+        Stream::new(Source { index: index, port: 0 }, registrar, scope)
+    }
+}
+
+/// The `Operate` implementation backing `unary_notify` and `unary_stream`.
+struct UnaryOperator<T: Timestamp, D1, D2, L>
+where L: FnMut(&mut InputHandle<T, Vec<D1>>, &mut OutputHandle<T, Vec<D2>, Tee<T, Vec<D2>>>, &mut Notificator<T>)+'static {
+    name: String,
+    address: Vec<usize>,
+    pull_counter: PullCounter<T, Vec<D1>>,
+    push_buffer: PushBuffer<T, Vec<D2>, PushCounter<T, Vec<D2>, Tee<T, Vec<D2>>>>,
+    internal: Rc<RefCell<CountMap<T>>>,
+    frontier: MutableAntichain<T>,
+    notify: Vec<T>,
+    logic: L,
+}
+
+impl<T: Timestamp, D1, D2, L> Operate<T> for UnaryOperator<T, D1, D2, L>
+where L: FnMut(&mut InputHandle<T, Vec<D1>>, &mut OutputHandle<T, Vec<D2>, Tee<T, Vec<D2>>>, &mut Notificator<T>)+'static {
+
+    fn name(&self) -> String { self.name.clone() }
+    fn inputs(&self) -> usize { 1 }
+    fn outputs(&self) -> usize { 1 }
+
+    fn get_internal_summary(&mut self) -> (Vec<Vec<Antichain<T::Summary>>>, Vec<CountMap<T>>) {
+        let mut initial = CountMap::new();
+        for time in self.notify.drain(..) {
+            initial.update(&time, 1);
+        }
+        (vec![vec![Antichain::from_elem(Default::default())]], vec![initial])
+    }
+
+    fn push_external_progress(&mut self, external: &mut [CountMap<T>]) {
+        for (time, delta) in external[0].drain() {
+            self.frontier.update_weight(&time, delta, &mut CountMap::new());
+        }
+    }
+
+    fn pull_internal_progress(&mut self, internal: &mut [CountMap<T>],
+                                          consumed: &mut [CountMap<T>],
+                                          produced: &mut [CountMap<T>]) -> bool {
+        let mut input_handle = new_input_handle(&mut self.pull_counter, self.internal.clone(), &self.address);
+        let mut output_handle = new_output_handle(&mut self.push_buffer);
+        let mut notificator = Notificator::new(&self.frontier);
+
+        ::logging::log(&::logging::OPERATOR_SCHEDULE, &self.address, true);
+        (self.logic)(&mut input_handle, &mut output_handle, &mut notificator);
+        ::logging::log(&::logging::OPERATOR_SCHEDULE, &self.address, false);
+        ::logging::log_counts(&self.address, input_handle.consumed());
+
+        self.push_buffer.cease();
+        self.pull_counter.pull_progress(&mut consumed[0]);
+        self.push_buffer.inner().pull_progress(&mut produced[0]);
+        self.internal.borrow_mut().drain_into(&mut internal[0]);
+
+        false
+    }
+}
+
+/// The `Operate` implementation backing `unary_frontier`.
+struct UnaryFrontierOperator<T: Timestamp, D1, D2, L>
+where L: FnMut(&mut FrontieredInputHandle<T, Vec<D1>>, &mut OutputHandle<T, Vec<D2>, Tee<T, Vec<D2>>>)+'static {
+    name: String,
+    address: Vec<usize>,
+    pull_counter: PullCounter<T, Vec<D1>>,
+    push_buffer: PushBuffer<T, Vec<D2>, PushCounter<T, Vec<D2>, Tee<T, Vec<D2>>>>,
+    internal: Rc<RefCell<CountMap<T>>>,
+    frontier: MutableAntichain<T>,
+    logic: L,
+}
+
+impl<T: Timestamp, D1, D2, L> Operate<T> for UnaryFrontierOperator<T, D1, D2, L>
+where L: FnMut(&mut FrontieredInputHandle<T, Vec<D1>>, &mut OutputHandle<T, Vec<D2>, Tee<T, Vec<D2>>>)+'static {
+
+    fn name(&self) -> String { self.name.clone() }
+    fn inputs(&self) -> usize { 1 }
+    fn outputs(&self) -> usize { 1 }
+
+    fn get_internal_summary(&mut self) -> (Vec<Vec<Antichain<T::Summary>>>, Vec<CountMap<T>>) {
+        (vec![vec![Antichain::from_elem(Default::default())]], vec![CountMap::new()])
+    }
+
+    fn push_external_progress(&mut self, external: &mut [CountMap<T>]) {
+        for (time, delta) in external[0].drain() {
+            self.frontier.update_weight(&time, delta, &mut CountMap::new());
+        }
+    }
+
+    fn pull_internal_progress(&mut self, internal: &mut [CountMap<T>],
+                                          consumed: &mut [CountMap<T>],
+                                          produced: &mut [CountMap<T>]) -> bool {
+        let frontier = &self.frontier;
+        let mut raw_input = new_input_handle(&mut self.pull_counter, self.internal.clone(), &self.address);
+        let mut input_handle = FrontieredInputHandle::new(&mut raw_input, frontier);
+        let mut output_handle = new_output_handle(&mut self.push_buffer);
+
+        ::logging::log(&::logging::OPERATOR_SCHEDULE, &self.address, true);
+        (self.logic)(&mut input_handle, &mut output_handle);
+        ::logging::log(&::logging::OPERATOR_SCHEDULE, &self.address, false);
+        ::logging::log_counts(&self.address, input_handle.handle.consumed());
+
+        self.push_buffer.cease();
+        self.pull_counter.pull_progress(&mut consumed[0]);
+        self.push_buffer.inner().pull_progress(&mut produced[0]);
+        self.internal.borrow_mut().drain_into(&mut internal[0]);
+
+        false
+    }
+}
@@ -1,8 +1,37 @@
 //! Starts a timely dataflow execution from configuration information and per-worker logic.
 
+use std::time::Duration;
+
 use timely_communication::{initialize, Configuration, Allocator, WorkerGuards};
 use dataflow::scopes::{Root, Child, Scope};
 
+impl<A: Allocator> Root<A> {
+    /// Performs one step of the computation, parking the worker thread once no dataflow has any
+    /// work left to do.
+    ///
+    /// Parking is gated on `step`'s result directly: `step` returns `false` only once every
+    /// dataflow has been fully shut down, at which point there is no more local work this worker
+    /// could possibly perform, so waiting on the allocator for a message from a peer is the only
+    /// way it could still have something to do. While any dataflow remains active, `step` keeps
+    /// returning `true` and this call keeps driving it without parking in between.
+    ///
+    /// `timeout` bounds how long the worker may park waiting on the allocator to signal that
+    /// messages have arrived: `None` parks indefinitely, while `Some(Duration::new(0, 0))`
+    /// returns immediately without parking. The allocator's wakeup returns immediately if
+    /// messages are already available, so it is always safe to call after a step, whether or
+    /// not that step did anything.
+    ///
+    /// Returns the same as `step`: `true` if any dataflow remains active, `false` once all have
+    /// been shut down.
+    pub fn step_or_park(&mut self, timeout: Option<Duration>) -> bool {
+        let active = self.step();
+        if !active {
+            self.allocator().await_events(timeout);
+        }
+        active
+    }
+}
+
 /// Executes a single-threaded timely dataflow computation.
 ///
 /// The `example` method takes a closure on a `Scope` which it executes to initialize and run a
@@ -23,7 +52,7 @@ where F: Fn(&mut Child<Root<Allocator>, u64>)+Send+Sync+'static {
     initialize(Configuration::Thread, move |allocator| {
         let mut root = Root::new(allocator);
         root.scoped::<u64,_,_>(|x| func(x));
-        while root.step() { }
+        while root.step_or_park(None) { }
     }).unwrap();
 }
 
@@ -33,6 +62,12 @@ where F: Fn(&mut Child<Root<Allocator>, u64>)+Send+Sync+'static {
 /// workers threads, each of which execute the supplied closure to construct
 /// and run a timely dataflow computation.
 ///
+/// Workers drive their dataflow via `step_or_park`, which parks an idle thread rather than
+/// spinning until the communication layer wakes it with incoming messages or progress.
+///
+/// Each worker may register a `logging::LogEvent` sink via `logging::register` from within
+/// `func`, to observe operator scheduling and message-guard events as the computation runs.
+///
 /// The closure may return a `T: Send+'static`, and `execute` returns a result
 /// containing a `WorkerGuards<T>` (or error information), which can be joined
 /// to recover the result `T` values from the local workers.
@@ -55,7 +90,7 @@ where F: Fn(&mut Root<Allocator>)->T+Send+Sync+'static {
     initialize(config, move |allocator| {
         let mut root = Root::new(allocator);
         let result = func(&mut root);
-        while root.step() { }
+        while root.step_or_park(None) { }
         result
     })
 }
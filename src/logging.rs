@@ -0,0 +1,116 @@
+//! Pluggable, typed logging of timely dataflow's internal events.
+//!
+//! Operators and the scheduler already narrate their own activity by calling `log` around
+//! guarded regions (a `for_each` invocation, an operator's turn to run); by default nobody is
+//! listening. Calling `register` with a closure turns those calls into a live event stream,
+//! which a program can print, aggregate, or feed into a second timely dataflow for profiling.
+
+use std::cell::RefCell;
+use std::time::Instant;
+
+/// A single structured log record.
+#[derive(Debug, Clone)]
+pub struct LogEvent {
+    /// Wall-clock time at which the event was recorded.
+    pub time: Instant,
+    /// The address (as per `OperatorInfo::address`) of the scope containing the operator this
+    /// record concerns. Operators in the same scope report the same `addr`, so records from
+    /// sibling operators are not told apart by this field.
+    pub addr: Vec<usize>,
+    /// The named event stream this record belongs to, e.g. `GUARDED_MESSAGE`.
+    pub name: &'static str,
+    /// `true` on entry to the named region, `false` on exit.
+    ///
+    /// Events that do not bracket a region (for example `CHANNEL_COUNTS`) always report `true`.
+    pub is_start: bool,
+    /// For `CHANNEL_COUNTS`, the number of records the operator read from its input channel(s)
+    /// this turn. `None` for events that don't carry counts, and for operators with no inputs.
+    pub counts: Option<usize>,
+}
+
+/// The event stream bracketing each `for_each` invocation's user logic.
+pub static GUARDED_MESSAGE: &'static str = "guarded message";
+
+/// The event stream bracketing an operator's turn to run (its `pull_internal_progress` call).
+pub static OPERATOR_SCHEDULE: &'static str = "operator schedule";
+
+/// The event stream reporting how many records an operator read from its input channel(s)
+/// during its most recent turn.
+pub static CHANNEL_COUNTS: &'static str = "channel counts";
+
+thread_local! {
+    static LOGGER: RefCell<Option<Box<FnMut(LogEvent)>>> = RefCell::new(None);
+}
+
+/// Registers `logger` as this worker's logging sink, replacing any previously registered one.
+///
+/// Typically called once, from inside the closure passed to `execute`, before any operators
+/// have run.
+///
+/// #Examples
+/// ```
+/// timely::logging::register(|event| {
+///     println!("{:?}", event);
+/// });
+/// ```
+pub fn register<F: FnMut(LogEvent)+'static>(logger: F) {
+    LOGGER.with(|slot| {
+        *slot.borrow_mut() = Some(Box::new(logger));
+    });
+}
+
+/// Removes this worker's logging sink, if one is registered.
+pub fn unregister() {
+    LOGGER.with(|slot| {
+        *slot.borrow_mut() = None;
+    });
+}
+
+/// Records a bracketing event on the named stream, tagged with the address of the operator it
+/// concerns.
+///
+/// Takes `name` by reference to the `&'static str` constant (e.g. `&GUARDED_MESSAGE`) so that
+/// call sites need not move or copy the tag. Builds no `LogEvent` (no `addr.to_vec()`, no
+/// `Instant::now()`) unless a logger is actually registered, so calling this on the hot path
+/// with no logger attached costs only the no-op `RefCell` check.
+#[inline]
+pub fn log(name: &&'static str, addr: &[usize], is_start: bool) {
+    with_logger(|| LogEvent {
+        time: Instant::now(),
+        addr: addr.to_vec(),
+        name: *name,
+        is_start: is_start,
+        counts: None,
+    });
+}
+
+/// Records the number of records an operator read from its input channel(s) this turn, tagged
+/// with the operator's address. Like `log`, builds nothing unless a logger is registered.
+#[inline]
+pub fn log_counts(addr: &[usize], count: usize) {
+    with_logger(|| LogEvent {
+        time: Instant::now(),
+        addr: addr.to_vec(),
+        name: CHANNEL_COUNTS,
+        is_start: true,
+        counts: Some(count),
+    });
+}
+
+/// Builds and hands an event to the registered logger, if any.
+///
+/// The logger is taken out of the thread-local slot before `build` runs or the logger is
+/// invoked, rather than called while `borrow_mut` is held on it. A logger that itself triggers
+/// logging (the documented use case of feeding this event stream into a second dataflow whose
+/// own operators call `log`) would otherwise reenter `with_logger` while the slot's `RefCell` is
+/// still borrowed and panic; with the logger taken out first, that reentrant call simply finds
+/// no logger registered and is a no-op instead. Taking the logger out first also means `build`
+/// only runs when there is somewhere for its event to go.
+#[inline]
+fn with_logger<F: FnOnce() -> LogEvent>(build: F) {
+    let taken = LOGGER.with(|slot| slot.borrow_mut().take());
+    if let Some(mut logger) = taken {
+        logger(build());
+        LOGGER.with(|slot| *slot.borrow_mut() = Some(logger));
+    }
+}